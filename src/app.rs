@@ -0,0 +1,77 @@
+//
+// Copyright © 2020 Haim Gelfenbeyn
+// This code is licensed under MIT license (see LICENSE.txt for details)
+//
+
+use anyhow::Result;
+
+use crate::configuration::{Configuration, LoggingConfiguration, SwitchDirection, UsbTrigger};
+use crate::logging;
+use crate::usb;
+
+pub struct App {
+    config: Configuration,
+}
+
+impl App {
+    /// `log_file` forces file logging on even if the config doesn't enable it explicitly
+    /// (used for the `--log-file` style invocation some platforms run display-switch under).
+    pub fn new(config_file: Option<std::path::PathBuf>, log_file: bool) -> Result<Self> {
+        let mut config = Configuration::load(config_file)?;
+        if log_file {
+            config
+                .logging
+                .get_or_insert_with(LoggingConfiguration::default)
+                .file
+                .enabled = true;
+        }
+        logging::init_logging(config.logging.as_ref())?;
+        Ok(Self { config })
+    }
+
+    pub fn run(&self) -> Result<()> {
+        let context = rusb::Context::new()?;
+        let triggers = self.config.triggers();
+        info!(
+            "Watching {} USB trigger(s) for hotplug events",
+            triggers.len()
+        );
+        let _registration = usb::watch(&context, triggers, |trigger, direction| {
+            self.handle_switch(trigger, direction);
+        })?;
+        loop {
+            context.handle_events(None)?;
+        }
+    }
+
+    /// Dispatches a hotplug event for the trigger whose device matched: runs its exec hook, if
+    /// configured, for every monitor known to be connected.
+    fn handle_switch(&self, trigger: &dyn UsbTrigger, direction: SwitchDirection) {
+        for monitor_id in self.connected_monitor_ids() {
+            let sources = trigger.configuration_for_monitor(&monitor_id);
+            if let Some(hook) = sources.exec_hook(direction) {
+                if let Err(err) =
+                    crate::hooks::run(hook, direction, &monitor_id, sources.source(direction))
+                {
+                    warn!("Hook command failed for monitor {}: {:?}", monitor_id, err);
+                }
+            }
+            match sources.source(direction) {
+                Some(input) => info!(
+                    "Switching monitor {} to {:?} on {}",
+                    monitor_id, input, direction
+                ),
+                None => debug!(
+                    "No input source configured for monitor {} on {}",
+                    monitor_id, direction
+                ),
+            }
+        }
+    }
+
+    fn connected_monitor_ids(&self) -> Vec<String> {
+        // Monitor enumeration and the actual DDC/CI switch live in `display_control`, which this
+        // snapshot of the tree does not include.
+        Vec::new()
+    }
+}
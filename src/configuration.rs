@@ -5,6 +5,7 @@
 
 use crate::input_source::InputSource;
 use anyhow::{anyhow, Context, Result};
+use log::LevelFilter;
 use serde::{Deserialize, Deserializer};
 use std::fmt;
 
@@ -14,33 +15,253 @@ pub enum SwitchDirection {
     Disconnect,
 }
 
-#[derive(Debug, Deserialize, Copy, Clone)]
+/// Matches a USB device either by its plain `"vid:pid"` string (for compatibility) or by a
+/// `[usb_device]` table of individually-optional fields, all of which must match.
+#[derive(Debug, Clone)]
+pub enum UsbDeviceFilter {
+    Simple(String),
+    Detailed {
+        vendor_id: Option<u16>,
+        product_id: Option<u16>,
+        serial_number: Option<String>,
+        bus_path: Option<String>,
+    },
+}
+
+impl UsbDeviceFilter {
+    pub fn matches(
+        &self,
+        vendor_id: u16,
+        product_id: u16,
+        serial_number: Option<&str>,
+        bus_path: Option<&str>,
+    ) -> bool {
+        match self {
+            UsbDeviceFilter::Simple(vid_pid) => {
+                *vid_pid == format!("{:04x}:{:04x}", vendor_id, product_id)
+            }
+            UsbDeviceFilter::Detailed {
+                vendor_id: want_vendor,
+                product_id: want_product,
+                serial_number: want_serial,
+                bus_path: want_bus_path,
+            } => {
+                want_vendor.map_or(true, |want| want == vendor_id)
+                    && want_product.map_or(true, |want| want == product_id)
+                    && want_serial.as_deref().map_or(true, |want| {
+                        serial_number.map_or(false, |actual| actual.eq_ignore_ascii_case(want))
+                    })
+                    && want_bus_path
+                        .as_deref()
+                        .map_or(true, |want| bus_path == Some(want))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
 pub struct InputSources {
     // Note: Serde alias won't work here, because of https://github.com/serde-rs/serde/issues/1504
     // So cannot alias "on_usb_connect" to "monitor_input"
     pub on_usb_connect: Option<InputSource>,
     pub on_usb_disconnect: Option<InputSource>,
+    pub on_usb_connect_exec: Option<String>,
+    pub on_usb_disconnect_exec: Option<String>,
+}
+
+/// What `app` should watch the USB bus for: either the implicit single device bound by the
+/// top-level `usb_device`/monitor fields, or the explicit `[[trigger]]` entries. Building this
+/// enum is the only way to construct a `Configuration`, so "a trigger with no device to match"
+/// can't be represented, let alone observed later by code that treats `Configuration` as a trigger.
+#[derive(Debug)]
+enum Triggers {
+    Single(UsbDeviceFilter),
+    Explicit(Vec<Trigger>),
 }
 
 #[derive(Debug, Deserialize)]
-struct PerMonitorConfiguration {
-    monitor_id: String,
+#[serde(try_from = "RawConfiguration")]
+pub struct Configuration {
+    triggers: Triggers,
+    input_sources: InputSources,
+    // Keyed by an (arbitrary-length) substring of the monitor id to match against, e.g.
+    // `[monitors.1e6d]` as a TOML table or INI section, or a `1e6d` key under `monitors:` in YAML.
+    monitor_overrides: std::collections::HashMap<String, InputSources>,
+    pub logging: Option<LoggingConfiguration>,
+}
+
+/// Mirrors the on-disk shape of `Configuration` field-for-field; deserialized first so
+/// `TryFrom<RawConfiguration>` can fold `usb_device` and `trigger` into a single `Triggers`,
+/// rejecting a config that sets neither at construction time rather than relying on callers to
+/// check afterwards.
+#[derive(Debug, Deserialize)]
+struct RawConfiguration {
+    // Optional because it is made redundant by `[[trigger]]` tables (see `explicit_triggers`
+    // below): a config using only triggers has no need for a top-level device to bind.
+    #[serde(
+        default,
+        deserialize_with = "Configuration::deserialize_optional_usb_device"
+    )]
+    usb_device: Option<UsbDeviceFilter>,
     #[serde(flatten)]
     input_sources: InputSources,
+    #[serde(default, rename = "monitors")]
+    monitor_overrides: std::collections::HashMap<String, InputSources>,
+    // Repeated `[[trigger]]` tables let power users bind several independent USB devices to
+    // independent monitor actions. The top-level `usb_device`/monitor fields above remain the
+    // one-trigger special case used when this list is empty.
+    #[serde(default, rename = "trigger")]
+    explicit_triggers: Vec<Trigger>,
+    logging: Option<LoggingConfiguration>,
 }
 
+impl std::convert::TryFrom<RawConfiguration> for Configuration {
+    type Error = String;
+
+    fn try_from(raw: RawConfiguration) -> Result<Self, Self::Error> {
+        let triggers = if !raw.explicit_triggers.is_empty() {
+            Triggers::Explicit(raw.explicit_triggers)
+        } else if let Some(usb_device) = raw.usb_device {
+            Triggers::Single(usb_device)
+        } else {
+            return Err(
+                "configuration must specify either `usb_device` or at least one [[trigger]]"
+                    .to_string(),
+            );
+        };
+        Ok(Self {
+            triggers,
+            input_sources: raw.input_sources,
+            monitor_overrides: raw.monitor_overrides,
+            logging: raw.logging,
+        })
+    }
+}
+
+/// One independent USB-device-to-monitor-action binding, as configured via a `[[trigger]]` table.
 #[derive(Debug, Deserialize)]
-pub struct Configuration {
+pub struct Trigger {
     #[serde(deserialize_with = "Configuration::deserialize_usb_device")]
-    pub usb_device: String,
+    pub usb_device: UsbDeviceFilter,
     #[serde(flatten)]
     input_sources: InputSources,
-    monitor1: Option<PerMonitorConfiguration>,
-    monitor2: Option<PerMonitorConfiguration>,
-    monitor3: Option<PerMonitorConfiguration>,
-    monitor4: Option<PerMonitorConfiguration>,
-    monitor5: Option<PerMonitorConfiguration>,
-    monitor6: Option<PerMonitorConfiguration>,
+    #[serde(default, rename = "monitors")]
+    monitor_overrides: std::collections::HashMap<String, InputSources>,
+}
+
+/// Common behavior shared by the top-level `Configuration` (the implicit single trigger) and
+/// each explicit `[[trigger]]` entry, so `app` can treat both uniformly.
+pub trait UsbTrigger {
+    fn usb_device_filter(&self) -> &UsbDeviceFilter;
+    fn configuration_for_monitor(&self, monitor_id: &str) -> InputSources;
+
+    fn matches_device(
+        &self,
+        vendor_id: u16,
+        product_id: u16,
+        serial_number: Option<&str>,
+        bus_path: Option<&str>,
+    ) -> bool {
+        self.usb_device_filter()
+            .matches(vendor_id, product_id, serial_number, bus_path)
+    }
+}
+
+impl UsbTrigger for Trigger {
+    fn usb_device_filter(&self) -> &UsbDeviceFilter {
+        &self.usb_device
+    }
+
+    fn configuration_for_monitor(&self, monitor_id: &str) -> InputSources {
+        resolve_monitor_override(monitor_id, &self.monitor_overrides, &self.input_sources)
+    }
+}
+
+impl UsbTrigger for Configuration {
+    fn usb_device_filter(&self) -> &UsbDeviceFilter {
+        match &self.triggers {
+            Triggers::Single(usb_device) => usb_device,
+            // `triggers()` never puts `self` in the returned list when holding `Explicit`
+            // triggers, so this arm can't be reached.
+            Triggers::Explicit(_) => unreachable!("Configuration has no device of its own"),
+        }
+    }
+
+    fn configuration_for_monitor(&self, monitor_id: &str) -> InputSources {
+        self.configuration_for_monitor(monitor_id)
+    }
+}
+
+fn resolve_monitor_override(
+    monitor_id: &str,
+    overrides: &std::collections::HashMap<String, InputSources>,
+    default: &InputSources,
+) -> InputSources {
+    // `HashMap` iteration order is randomized per-process, so when more than one configured key
+    // matches (e.g. both "1" and "12" match monitor id "123"), break the tie deterministically:
+    // prefer the longest (most specific) match, then fall back to key text so that even equal-length
+    // matches don't depend on the hasher's visiting order.
+    let matched = overrides
+        .iter()
+        .filter(|(id, _)| monitor_id.to_lowercase().contains(&id.to_lowercase()))
+        .max_by_key(|(id, _)| (id.len(), id.clone()))
+        .map(|(_, config)| config);
+    matched.map_or_else(|| default.clone(), |config| config.merge(default))
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct LoggingFile {
+    #[serde(default)]
+    pub enabled: bool,
+    pub directory: Option<std::path::PathBuf>,
+    #[serde(default)]
+    pub append: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LoggingConfiguration {
+    #[serde(
+        default = "LoggingConfiguration::default_level",
+        deserialize_with = "LoggingConfiguration::deserialize_level"
+    )]
+    pub level: LevelFilter,
+    #[serde(default)]
+    pub file: LoggingFile,
+}
+
+impl Default for LoggingConfiguration {
+    fn default() -> Self {
+        Self {
+            level: Self::default_level(),
+            file: LoggingFile::default(),
+        }
+    }
+}
+
+impl LoggingConfiguration {
+    fn default_level() -> LevelFilter {
+        LevelFilter::Debug
+    }
+
+    fn deserialize_level<'de, D>(deserializer: D) -> Result<LevelFilter, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s: String = Deserialize::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|_| serde::de::Error::custom(format!("invalid log level: {}", s)))
+    }
+
+    pub fn log_file_name(&self) -> Result<std::path::PathBuf> {
+        self.file
+            .directory
+            .clone()
+            .map_or_else(Configuration::log_file_name, |directory| {
+                std::fs::create_dir_all(&directory)
+                    .with_context(|| format!("failed to create directory: {:?}", directory))?;
+                Ok(directory.join("display-switch.log"))
+            })
+    }
 }
 
 impl fmt::Display for SwitchDirection {
@@ -52,17 +273,19 @@ impl fmt::Display for SwitchDirection {
     }
 }
 
-impl PerMonitorConfiguration {
-    fn matches(&self, monitor_id: &str) -> bool {
-        monitor_id.to_lowercase().contains(&self.monitor_id.to_lowercase())
-    }
-}
-
 impl InputSources {
     fn merge(&self, default: &Self) -> Self {
         Self {
             on_usb_connect: self.on_usb_connect.or(default.on_usb_connect),
             on_usb_disconnect: self.on_usb_disconnect.or(default.on_usb_disconnect),
+            on_usb_connect_exec: self
+                .on_usb_connect_exec
+                .clone()
+                .or_else(|| default.on_usb_connect_exec.clone()),
+            on_usb_disconnect_exec: self
+                .on_usb_disconnect_exec
+                .clone()
+                .or_else(|| default.on_usb_disconnect_exec.clone()),
         }
     }
 
@@ -72,6 +295,13 @@ impl InputSources {
             SwitchDirection::Disconnect => self.on_usb_disconnect,
         }
     }
+
+    pub fn exec_hook(&self, direction: SwitchDirection) -> Option<&str> {
+        match direction {
+            SwitchDirection::Connect => self.on_usb_connect_exec.as_deref(),
+            SwitchDirection::Disconnect => self.on_usb_disconnect_exec.as_deref(),
+        }
+    }
 }
 
 impl Configuration {
@@ -81,21 +311,105 @@ impl Configuration {
         } else {
             Self::config_file_name()?
         };
+        let format = Self::format_for_file(&config_file_name)?;
         let mut settings = config::Config::default();
         settings
-            .merge(config::File::from(config_file_name.clone()))?
+            .merge(config::File::new(
+                config_file_name.to_str().ok_or_else(|| {
+                    anyhow!(
+                        "config file path is not valid UTF-8: {:?}",
+                        config_file_name
+                    )
+                })?,
+                format,
+            ))?
             .merge(config::Environment::with_prefix("DISPLAY_SWITCH"))?;
         let config = settings.try_into::<Self>()?;
-        info!("Configuration loaded ({:?}): {:?}", config_file_name, config);
+        info!(
+            "Configuration loaded ({:?}): {:?}",
+            config_file_name, config
+        );
         Ok(config)
     }
 
-    fn deserialize_usb_device<'de, D>(deserializer: D) -> Result<String, D::Error>
+    /// Picks the `config` crate parser to use based on the file extension, defaulting to the
+    /// original INI format when there is none. Structured data like monitor lists reads much
+    /// more naturally in TOML or YAML, so both are supported alongside the legacy INI format.
+    fn format_for_file(path: &std::path::Path) -> Result<config::FileFormat> {
+        use config::FileFormat;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Ok(FileFormat::Toml),
+            Some("yaml") | Some("yml") => Ok(FileFormat::Yaml),
+            Some("ini") | None => Ok(FileFormat::Ini),
+            Some(other) => Err(anyhow!(
+                "unsupported configuration file extension: {}",
+                other
+            )),
+        }
+    }
+
+    fn deserialize_usb_device<'de, D>(deserializer: D) -> Result<UsbDeviceFilter, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let s: String = Deserialize::deserialize(deserializer)?;
-        Ok(s.to_lowercase())
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Simple(String),
+            Detailed {
+                vendor_id: Option<String>,
+                product_id: Option<String>,
+                serial_number: Option<String>,
+                bus_path: Option<String>,
+            },
+        }
+
+        fn parse_hex_id<E: serde::de::Error>(field: &str, value: &str) -> Result<u16, E> {
+            u16::from_str_radix(value.trim_start_matches("0x"), 16)
+                .map_err(|_| E::custom(format!("invalid {}: {}", field, value)))
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Simple(s) => Ok(UsbDeviceFilter::Simple(s.to_lowercase())),
+            Raw::Detailed {
+                vendor_id,
+                product_id,
+                serial_number,
+                bus_path,
+            } => {
+                if vendor_id.is_none()
+                    && product_id.is_none()
+                    && serial_number.is_none()
+                    && bus_path.is_none()
+                {
+                    return Err(serde::de::Error::custom(
+                        "[usb_device] must set at least one of vendor_id, product_id, \
+                         serial_number, bus_path, otherwise it would match every USB device",
+                    ));
+                }
+                Ok(UsbDeviceFilter::Detailed {
+                    vendor_id: vendor_id
+                        .map(|v| parse_hex_id("vendor_id", &v))
+                        .transpose()?,
+                    product_id: product_id
+                        .map(|v| parse_hex_id("product_id", &v))
+                        .transpose()?,
+                    serial_number,
+                    bus_path,
+                })
+            }
+        }
+    }
+
+    // `deserialize_with` is only invoked when the field is present (absence is handled by
+    // `#[serde(default)]` instead), so this can delegate straight to the required-field parser.
+    fn deserialize_optional_usb_device<'de, D>(
+        deserializer: D,
+    ) -> Result<Option<UsbDeviceFilter>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::deserialize_usb_device(deserializer).map(Some)
     }
 
     pub fn config_file_name() -> Result<std::path::PathBuf> {
@@ -123,30 +437,25 @@ impl Configuration {
                 .ok_or_else(|| anyhow!("Data-local directory not found"))?
                 .join("display-switch")
         };
-        std::fs::create_dir_all(&log_dir).with_context(|| format!("failed to create directory: {:?}", log_dir))?;
+        std::fs::create_dir_all(&log_dir)
+            .with_context(|| format!("failed to create directory: {:?}", log_dir))?;
         Ok(log_dir.join("display-switch.log"))
     }
 
     pub fn configuration_for_monitor(&self, monitor_id: &str) -> InputSources {
-        // Find a matching per-monitor config, if there is any
-        let per_monitor_config = [
-            &self.monitor1,
-            &self.monitor2,
-            &self.monitor3,
-            &self.monitor4,
-            &self.monitor5,
-            &self.monitor6,
-        ]
-        .iter()
-        .find_map(|config| {
-            config
-                .as_ref()
-                .and_then(|config| if config.matches(monitor_id) { Some(config) } else { None })
-        });
-        // Merge global config as needed
-        per_monitor_config.map_or(self.input_sources, |config| {
-            config.input_sources.merge(&self.input_sources)
-        })
+        resolve_monitor_override(monitor_id, &self.monitor_overrides, &self.input_sources)
+    }
+
+    /// Returns the triggers `app` should watch for hotplug events: the explicit `[[trigger]]`
+    /// entries if any were configured, or the single top-level `usb_device` binding otherwise.
+    pub fn triggers(&self) -> Vec<&dyn UsbTrigger> {
+        match &self.triggers {
+            Triggers::Single(_) => vec![self],
+            Triggers::Explicit(triggers) => triggers
+                .iter()
+                .map(|trigger| trigger as &dyn UsbTrigger)
+                .collect(),
+        }
     }
 }
 
@@ -163,12 +472,91 @@ mod tests {
         assert!(file_name.unwrap().ends_with("display-switch.log"))
     }
 
-    fn load_test_config(config_str: &str) -> Result<Configuration, ConfigError> {
+    #[test]
+    fn test_logging_configuration_log_file_name_creates_missing_directory() {
+        let directory = std::env::temp_dir().join(format!(
+            "display-switch-test-log-dir-{:?}",
+            std::thread::current().id()
+        ));
+        let _ = std::fs::remove_dir_all(&directory);
+        let logging = LoggingConfiguration {
+            level: LevelFilter::Debug,
+            file: LoggingFile {
+                enabled: true,
+                directory: Some(directory.clone()),
+                append: false,
+            },
+        };
+        let file_name = logging.log_file_name().unwrap();
+        assert!(directory.is_dir());
+        assert_eq!(file_name, directory.join("display-switch.log"));
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_logging_config_defaults_to_none() {
+        let config = load_test_config(
+            r#"
+            usb_device = "dead:BEEF"
+            on_usb_connect = "DisplayPort2"
+        "#,
+        )
+        .unwrap();
+        assert!(config.logging.is_none());
+    }
+
+    #[test]
+    fn test_logging_config_deserialization() {
+        let config = load_test_config(
+            r#"
+            usb_device = "dead:BEEF"
+            on_usb_connect = "DisplayPort2"
+
+            [logging]
+            level = "info"
+
+            [logging.file]
+            enabled = true
+            append = true
+        "#,
+        )
+        .unwrap();
+        let logging = config.logging.unwrap();
+        assert_eq!(logging.level, LevelFilter::Info);
+        assert!(logging.file.enabled);
+        assert!(logging.file.append);
+    }
+
+    fn load_test_config_with_format(
+        config_str: &str,
+        format: config::FileFormat,
+    ) -> Result<Configuration, ConfigError> {
         let mut settings = config::Config::default();
-        settings.merge(config::File::from_str(config_str, Ini)).unwrap();
+        settings
+            .merge(config::File::from_str(config_str, format))
+            .unwrap();
         settings.try_into::<Configuration>()
     }
 
+    fn load_test_config(config_str: &str) -> Result<Configuration, ConfigError> {
+        load_test_config_with_format(config_str, Ini)
+    }
+
+    #[test]
+    fn test_config_without_usb_device_or_trigger_is_rejected() {
+        // Goes through `try_into` directly, the same path `load()` uses, so this pins the
+        // invariant to construction itself rather than to a check only `load()` happens to make.
+        let error = load_test_config(
+            r#"
+            on_usb_connect = "DisplayPort2"
+        "#,
+        )
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("must specify either `usb_device` or at least one [[trigger]]"));
+    }
+
     #[test]
     fn test_usb_device_deserialization() {
         let config = load_test_config(
@@ -178,7 +566,74 @@ mod tests {
         "#,
         )
         .unwrap();
-        assert_eq!(config.usb_device, "dead:beef")
+        let usb_device = config.triggers()[0].usb_device_filter().clone();
+        assert!(usb_device.matches(0xdead, 0xbeef, None, None));
+        assert!(!usb_device.matches(0xdead, 0xbee0, None, None));
+    }
+
+    #[test]
+    fn test_detailed_usb_device_deserialization() {
+        let config = load_test_config(
+            r#"
+            on_usb_connect = "DisplayPort2"
+
+            [usb_device]
+            vendor_id = "dead"
+            product_id = "beef"
+            serial_number = "ABC123"
+        "#,
+        )
+        .unwrap();
+        let usb_device = config.triggers()[0].usb_device_filter().clone();
+        // serial_number matches case-insensitively, bus_path is unconstrained
+        assert!(usb_device.matches(0xdead, 0xbeef, Some("abc123"), Some("1-4.2")));
+        // product_id doesn't match
+        assert!(!usb_device.matches(0xdead, 0xbee0, Some("abc123"), None));
+        // serial_number doesn't match
+        assert!(!usb_device.matches(0xdead, 0xbeef, Some("other"), None));
+    }
+
+    #[test]
+    fn test_empty_detailed_usb_device_is_rejected() {
+        let error = load_test_config(
+            r#"
+            on_usb_connect = "DisplayPort2"
+
+            [usb_device]
+        "#,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("match every USB device"));
+    }
+
+    #[test]
+    fn test_exec_hook_deserialization_and_merge() {
+        let config = load_test_config(
+            r#"
+            usb_device = "dead:BEEF"
+            on_usb_connect = "0x10"
+            on_usb_connect_exec = "notify-send global-connect"
+
+            [monitors.123]
+            on_usb_connect_exec = "notify-send monitor-connect"
+        "#,
+        )
+        .unwrap();
+
+        // No per-monitor override: falls back to the global hook
+        assert_eq!(
+            config
+                .configuration_for_monitor("333")
+                .exec_hook(SwitchDirection::Connect),
+            Some("notify-send global-connect")
+        );
+        // Per-monitor hook overrides the global one
+        assert_eq!(
+            config
+                .configuration_for_monitor("1234")
+                .exec_hook(SwitchDirection::Connect),
+            Some("notify-send monitor-connect")
+        );
     }
 
     #[test]
@@ -192,7 +647,10 @@ mod tests {
         )
         .unwrap();
         assert_eq!(config.input_sources.on_usb_connect.unwrap().value(), 0x10);
-        assert_eq!(config.input_sources.on_usb_disconnect.unwrap().value(), 0x0f);
+        assert_eq!(
+            config.input_sources.on_usb_disconnect.unwrap().value(),
+            0x0f
+        );
     }
 
     #[test]
@@ -220,7 +678,10 @@ mod tests {
         )
         .unwrap();
         assert_eq!(config.input_sources.on_usb_connect.unwrap().value(), 0x10);
-        assert_eq!(config.input_sources.on_usb_disconnect.unwrap().value(), 0x20);
+        assert_eq!(
+            config.input_sources.on_usb_disconnect.unwrap().value(),
+            0x20
+        );
     }
 
     #[test]
@@ -231,12 +692,10 @@ mod tests {
             on_usb_connect = "0x10"
             on_usb_disconnect = "0x20"
 
-            [monitor1]
-            monitor_id = 123
+            [monitors.123]
             on_usb_connect = 0x11
 
-            [monitor2]
-            monitor_id = 45
+            [monitors.45]
             on_usb_connect = 0x12
             on_usb_disconnect = 0x13
         "#,
@@ -245,12 +704,20 @@ mod tests {
 
         // When no specific monitor matches, use the global defaults
         assert_eq!(
-            config.configuration_for_monitor("333").on_usb_connect.unwrap().value(),
+            config
+                .configuration_for_monitor("333")
+                .on_usb_connect
+                .unwrap()
+                .value(),
             0x10
         );
         // Matches monitor #1, and it should use its "on-connect" and global "on-disconnect"
         assert_eq!(
-            config.configuration_for_monitor("1234").on_usb_connect.unwrap().value(),
+            config
+                .configuration_for_monitor("1234")
+                .on_usb_connect
+                .unwrap()
+                .value(),
             0x11
         );
         assert_eq!(
@@ -263,7 +730,11 @@ mod tests {
         );
         // Matches monitor #2, and it should use its "on-connect" and "on-disconnect" values
         assert_eq!(
-            config.configuration_for_monitor("2345").on_usb_connect.unwrap().value(),
+            config
+                .configuration_for_monitor("2345")
+                .on_usb_connect
+                .unwrap()
+                .value(),
             0x12
         );
         assert_eq!(
@@ -275,4 +746,211 @@ mod tests {
             0x13
         );
     }
+
+    #[test]
+    fn test_more_than_six_monitor_overrides() {
+        let config = load_test_config(
+            r#"
+            usb_device = "dead:BEEF"
+            on_usb_connect = "0x10"
+
+            [monitors.1]
+            on_usb_connect = 0x01
+
+            [monitors.2]
+            on_usb_connect = 0x02
+
+            [monitors.3]
+            on_usb_connect = 0x03
+
+            [monitors.4]
+            on_usb_connect = 0x04
+
+            [monitors.5]
+            on_usb_connect = 0x05
+
+            [monitors.6]
+            on_usb_connect = 0x06
+
+            [monitors.7]
+            on_usb_connect = 0x07
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config
+                .configuration_for_monitor("7")
+                .on_usb_connect
+                .unwrap()
+                .value(),
+            0x07
+        );
+    }
+
+    #[test]
+    fn test_ambiguous_monitor_override_prefers_longest_match() {
+        let config = load_test_config(
+            r#"
+            usb_device = "dead:BEEF"
+            on_usb_connect = "0x10"
+
+            [monitors.1]
+            on_usb_connect = 0x01
+
+            [monitors.123]
+            on_usb_connect = 0x02
+        "#,
+        )
+        .unwrap();
+        // Both "1" and "123" match monitor id "123"; the more specific key should always win,
+        // regardless of HashMap iteration order.
+        assert_eq!(
+            config
+                .configuration_for_monitor("123")
+                .on_usb_connect
+                .unwrap()
+                .value(),
+            0x02
+        );
+    }
+
+    #[test]
+    fn test_monitor_override_tie_break_is_deterministic_for_equal_length_keys() {
+        let config = load_test_config(
+            r#"
+            usb_device = "dead:BEEF"
+            on_usb_connect = "0x10"
+
+            [monitors.12]
+            on_usb_connect = 0x01
+
+            [monitors.1e]
+            on_usb_connect = 0x02
+        "#,
+        )
+        .unwrap();
+        // Both "12" and "1e" are the same length and both match monitor id "1e12"; the result
+        // must not depend on HashMap iteration order, so pin it to the lexicographically greater
+        // key ("1e" > "12").
+        assert_eq!(
+            config
+                .configuration_for_monitor("1e12")
+                .on_usb_connect
+                .unwrap()
+                .value(),
+            0x02
+        );
+    }
+
+    #[test]
+    fn test_format_for_file() {
+        use config::FileFormat;
+        assert_eq!(
+            Configuration::format_for_file(std::path::Path::new("display-switch.toml")).unwrap(),
+            FileFormat::Toml
+        );
+        assert_eq!(
+            Configuration::format_for_file(std::path::Path::new("display-switch.yaml")).unwrap(),
+            FileFormat::Yaml
+        );
+        assert_eq!(
+            Configuration::format_for_file(std::path::Path::new("display-switch.ini")).unwrap(),
+            FileFormat::Ini
+        );
+        assert!(
+            Configuration::format_for_file(std::path::Path::new("display-switch.conf")).is_err()
+        );
+    }
+
+    fn load_test_toml_config(config_str: &str) -> Result<Configuration, ConfigError> {
+        load_test_config_with_format(config_str, config::FileFormat::Toml)
+    }
+
+    #[test]
+    fn test_single_usb_device_is_the_implicit_one_trigger_case() {
+        let config = load_test_config(
+            r#"
+            usb_device = "dead:BEEF"
+            on_usb_connect = "0x10"
+        "#,
+        )
+        .unwrap();
+        let triggers = config.triggers();
+        assert_eq!(triggers.len(), 1);
+        assert!(triggers[0].matches_device(0xdead, 0xbeef, None, None));
+    }
+
+    #[test]
+    fn test_multiple_triggers_dispatch_independently() {
+        let config = load_test_toml_config(
+            r#"
+            usb_device = "dead:beef"
+            on_usb_connect = "0x10"
+
+            [[trigger]]
+            usb_device = "1111:2222"
+            on_usb_connect = "0x11"
+
+            [[trigger]]
+            usb_device = "3333:4444"
+            on_usb_connect = "0x12"
+
+            [trigger.monitors.abc]
+            on_usb_connect = 0x13
+        "#,
+        )
+        .unwrap();
+
+        // Explicit triggers replace the implicit one-trigger case entirely
+        let triggers = config.triggers();
+        assert_eq!(triggers.len(), 2);
+
+        let keyboard_dongle = triggers
+            .iter()
+            .find(|t| t.matches_device(0x1111, 0x2222, None, None))
+            .unwrap();
+        assert_eq!(
+            keyboard_dongle
+                .configuration_for_monitor("anything")
+                .on_usb_connect
+                .unwrap()
+                .value(),
+            0x11
+        );
+
+        let dock = triggers
+            .iter()
+            .find(|t| t.matches_device(0x3333, 0x4444, None, None))
+            .unwrap();
+        assert_eq!(
+            dock.configuration_for_monitor("xyz")
+                .on_usb_connect
+                .unwrap()
+                .value(),
+            0x12
+        );
+        assert_eq!(
+            dock.configuration_for_monitor("abcdef")
+                .on_usb_connect
+                .unwrap()
+                .value(),
+            0x13
+        );
+    }
+
+    #[test]
+    fn test_triggers_only_config_without_top_level_usb_device() {
+        let config = load_test_toml_config(
+            r#"
+            [[trigger]]
+            usb_device = "1111:2222"
+            on_usb_connect = "0x11"
+        "#,
+        )
+        .unwrap();
+        assert!(matches!(config.triggers, Triggers::Explicit(_)));
+        let triggers = config.triggers();
+        assert_eq!(triggers.len(), 1);
+        assert!(triggers[0].matches_device(0x1111, 0x2222, None, None));
+    }
 }
@@ -0,0 +1,53 @@
+//
+// Copyright © 2020 Haim Gelfenbeyn
+// This code is licensed under MIT license (see LICENSE.txt for details)
+//
+
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use crate::configuration::SwitchDirection;
+use crate::input_source::InputSource;
+
+/// Runs a user-configured `on_usb_connect_exec` / `on_usb_disconnect_exec` command, exposing the
+/// switch context to the child process via environment variables.
+pub fn run(
+    command: &str,
+    direction: SwitchDirection,
+    monitor_id: &str,
+    input: Option<InputSource>,
+) -> Result<()> {
+    info!(
+        "Running hook for {} on monitor {}: {}",
+        direction, monitor_id, command
+    );
+    let mut shell = shell_command();
+    shell.arg(command);
+    shell.env("DISPLAY_SWITCH_DIRECTION", direction.to_string());
+    shell.env("DISPLAY_SWITCH_MONITOR_ID", monitor_id);
+    if let Some(input) = input {
+        shell.env("DISPLAY_SWITCH_INPUT", format!("{:#x}", input.value()));
+    }
+    let status = shell
+        .status()
+        .with_context(|| format!("failed to run hook command: {}", command))?;
+    if !status.success() {
+        warn!("Hook command exited with {}: {}", status, command);
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn shell_command() -> Command {
+    let mut command = Command::new("cmd");
+    command.arg("/C");
+    command
+}
+
+#[cfg(not(target_os = "windows"))]
+fn shell_command() -> Command {
+    let mut command = Command::new("sh");
+    command.arg("-c");
+    command
+}
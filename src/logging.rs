@@ -3,23 +3,33 @@
 // This code is licensed under MIT license (see LICENSE.txt for details)
 //
 
-use std::fs::File;
+use std::fs::{File, OpenOptions};
 
 use anyhow::Result;
 use simplelog::*;
 
-use crate::configuration::Configuration;
+use crate::configuration::LoggingConfiguration;
 
-pub fn init_logging(log_file: bool) -> Result<()> {
-    let mut loggers: Vec<Box<dyn SharedLogger>> = vec![
-        TermLogger::new(LevelFilter::Debug, Config::default(), TerminalMode::Mixed)];
-    if log_file {
-        loggers.push(WriteLogger::new(
-            LevelFilter::Debug,
-            Config::default(),
-            File::create(Configuration::log_file_name()?)?,
-        ))
-    };
+pub fn init_logging(logging: Option<&LoggingConfiguration>) -> Result<()> {
+    let level = logging.map_or(LevelFilter::Debug, |logging| logging.level);
+    let mut loggers: Vec<Box<dyn SharedLogger>> = vec![TermLogger::new(
+        level,
+        Config::default(),
+        TerminalMode::Mixed,
+    )];
+    if let Some(logging) = logging {
+        if logging.file.enabled {
+            let log_file = if logging.file.append {
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(logging.log_file_name()?)?
+            } else {
+                File::create(logging.log_file_name()?)?
+            };
+            loggers.push(WriteLogger::new(level, Config::default(), log_file))
+        }
+    }
     CombinedLogger::init(loggers)?;
 
     Ok(())
@@ -12,6 +12,7 @@ use anyhow::Result;
 mod app;
 mod configuration;
 mod display_control;
+mod hooks;
 mod input_source;
 mod logging;
 mod platform;
@@ -0,0 +1,137 @@
+//
+// Copyright © 2020 Haim Gelfenbeyn
+// This code is licensed under MIT license (see LICENSE.txt for details)
+//
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use rusb::UsbContext;
+
+use crate::configuration::{SwitchDirection, UsbTrigger};
+
+/// Identifying information read off a `rusb::Device` when a hotplug event fires. Kept separate
+/// from `rusb` types so `matching_trigger` can be exercised without a real USB bus.
+#[derive(Debug, Clone)]
+pub struct UsbDeviceIdentity {
+    pub vendor_id: u16,
+    pub product_id: u16,
+    pub serial_number: Option<String>,
+    pub bus_path: Option<String>,
+}
+
+impl UsbDeviceIdentity {
+    fn from_device(device: &rusb::Device<rusb::Context>) -> Result<Self> {
+        let descriptor = device
+            .device_descriptor()
+            .context("failed to read USB device descriptor")?;
+        let serial_number = device
+            .open()
+            .ok()
+            .and_then(|handle| handle.read_serial_number_string_ascii(&descriptor).ok());
+        let bus_path = device.port_numbers().ok().map(|ports| {
+            let ports = ports
+                .iter()
+                .map(u8::to_string)
+                .collect::<Vec<_>>()
+                .join(".");
+            format!("{}-{}", device.bus_number(), ports)
+        });
+        Ok(Self {
+            vendor_id: descriptor.vendor_id(),
+            product_id: descriptor.product_id(),
+            serial_number,
+            bus_path,
+        })
+    }
+}
+
+/// Finds the first configured trigger whose device filter matches the given device, if any.
+pub fn matching_trigger<'a>(
+    triggers: &[&'a dyn UsbTrigger],
+    device: &UsbDeviceIdentity,
+) -> Option<&'a dyn UsbTrigger> {
+    triggers.iter().copied().find(|trigger| {
+        trigger.matches_device(
+            device.vendor_id,
+            device.product_id,
+            device.serial_number.as_deref(),
+            device.bus_path.as_deref(),
+        )
+    })
+}
+
+struct HotplugHandler<'a> {
+    triggers: Vec<&'a dyn UsbTrigger>,
+    on_event: Box<dyn FnMut(&dyn UsbTrigger, SwitchDirection) + 'a>,
+    // A device can no longer be opened by the time `device_left` fires, so its serial number
+    // (which requires a control transfer) can't be read there. We stash the identity read while
+    // the device was still live on arrival, keyed by bus location, and reuse it on departure.
+    identities: RefCell<HashMap<(u8, u8), UsbDeviceIdentity>>,
+}
+
+impl<'a> HotplugHandler<'a> {
+    fn dispatch(&mut self, device: &rusb::Device<rusb::Context>, direction: SwitchDirection) {
+        let key = (device.bus_number(), device.address());
+        let identity = match direction {
+            SwitchDirection::Connect => match UsbDeviceIdentity::from_device(device) {
+                Ok(identity) => {
+                    self.identities.borrow_mut().insert(key, identity.clone());
+                    identity
+                }
+                Err(err) => {
+                    warn!("Failed to read USB device identity on connect: {:?}", err);
+                    return;
+                }
+            },
+            SwitchDirection::Disconnect => match self.identities.borrow_mut().remove(&key) {
+                Some(identity) => identity,
+                None => match UsbDeviceIdentity::from_device(device) {
+                    Ok(identity) => identity,
+                    Err(err) => {
+                        warn!(
+                            "Failed to read USB device identity on disconnect: {:?}",
+                            err
+                        );
+                        return;
+                    }
+                },
+            },
+        };
+        if let Some(trigger) = matching_trigger(&self.triggers, &identity) {
+            (self.on_event)(trigger, direction);
+        }
+    }
+}
+
+impl<'a> rusb::Hotplug<rusb::Context> for HotplugHandler<'a> {
+    fn device_arrived(&mut self, device: rusb::Device<rusb::Context>) {
+        self.dispatch(&device, SwitchDirection::Connect);
+    }
+
+    fn device_left(&mut self, device: rusb::Device<rusb::Context>) {
+        self.dispatch(&device, SwitchDirection::Disconnect);
+    }
+}
+
+/// Registers a single hotplug watch for the union of all configured trigger devices. Every
+/// USB arrival/removal is matched against `triggers` in our own callback (rather than filtering
+/// at the libusb level), and `on_event` is invoked with whichever trigger's filter matched.
+pub fn watch<'a>(
+    context: &'a rusb::Context,
+    triggers: Vec<&'a dyn UsbTrigger>,
+    on_event: impl FnMut(&dyn UsbTrigger, SwitchDirection) + 'a,
+) -> Result<rusb::Registration<rusb::Context>> {
+    rusb::HotplugBuilder::new()
+        .enumerate(true)
+        .register(
+            context,
+            Box::new(HotplugHandler {
+                triggers,
+                on_event: Box::new(on_event),
+                identities: RefCell::new(HashMap::new()),
+            }),
+        )
+        .context("failed to register USB hotplug callback")
+}